@@ -1,4 +1,4 @@
-use discordipc::{activity::{Activity, ActivityType, Assets}, Client, packet::Packet};
+use discordipc::{activity::{Activity, ActivityType, Assets, Timestamps}, Client, packet::Packet};
 use dotenv::dotenv;
 use mpris::{MetadataValue, PlaybackStatus, PlayerFinder};
 use musicbrainz_rs::entity::release::{Release, ReleaseSearchQuery};
@@ -10,8 +10,9 @@ use std::env;
 use std::num::ParseIntError;
 use std::error::Error;
 use std::sync::{Arc, Mutex, MutexGuard, RwLock};
-use std::time::Duration;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
 use tokio::time::sleep;
+use unicode_segmentation::UnicodeSegmentation;
 
 #[derive(Debug, thiserror::Error)]
 enum AppError {
@@ -24,9 +25,6 @@ enum AppError {
     #[error("Finding error: {0}")]
     Finding(#[from] mpris::FindingError),
 
-    #[error("MusicBrainz error: {0}")]
-    MusicBrainz(#[from] musicbrainz_rs::Error),
-
     #[error("No active players")]
     NoActivePlayers,
 
@@ -46,9 +44,32 @@ enum AppError {
     FieldNotFound(String),
 }
 
+// how the main loop should react to an AppError surfacing from process_metadata
+#[derive(Debug, PartialEq, Eq)]
+enum ErrorClass {
+    Expected,
+    Transient,
+    Fatal,
+}
+
+impl AppError {
+    fn classify(&self) -> ErrorClass {
+        match self {
+            AppError::NoActivePlayers | AppError::NoSongPlaying => ErrorClass::Expected,
+            AppError::DBus(_)
+            | AppError::Finding(_)
+            | AppError::FieldNotFound(_) => ErrorClass::Transient,
+            AppError::EnvVar(_)
+            | AppError::ParseInt(_)
+            | AppError::ParseError(_)
+            | AppError::TypeMismatch(_) => ErrorClass::Fatal,
+        }
+    }
+}
+
 #[derive(Debug, Default)]
 struct Config {
-    cache: RwLock<HashMap<String, ConfigValue>>,
+    cache: RwLock<HashMap<String, (Instant, ConfigValue)>>,
 }
 
 #[derive(Debug, Clone)]
@@ -63,6 +84,16 @@ static CONFIG: Lazy<Mutex<Config>> = Lazy::new(|| {
     Mutex::new(Config::new())
 });
 
+// reads cache_ttl straight from the environment (bypassing Config's own cache,
+// since Config::get can't re-lock CONFIG while it's already held)
+fn config_cache_ttl() -> Duration {
+    env::var("cache_ttl")
+        .ok()
+        .and_then(|raw| raw.parse::<u64>().ok())
+        .map(Duration::from_millis)
+        .unwrap_or(Duration::from_secs(3600))
+}
+
 impl Config {
     pub fn new() -> Self {
         Config {
@@ -74,9 +105,11 @@ impl Config {
         // check cache first
         {
             let cache = self.cache.read().unwrap();
-            if let Some(val) = cache.get(key) {
-                return T::try_from(val.clone())
-                    .map_err(|_| AppError::TypeMismatch(key.to_string()));
+            if let Some((stored_at, val)) = cache.get(key) {
+                if stored_at.elapsed() <= config_cache_ttl() {
+                    return T::try_from(val.clone())
+                        .map_err(|_| AppError::TypeMismatch(key.to_string()));
+                }
             }
         }
 
@@ -86,7 +119,7 @@ impl Config {
         // cache parsed value
         {
             let mut cache = self.cache.write().unwrap();
-            cache.insert(key.to_string(), parsed_value.clone());
+            cache.insert(key.to_string(), (Instant::now(), parsed_value.clone()));
         }
 
         T::try_from(parsed_value).map_err(|_| AppError::TypeMismatch(key.to_string()))
@@ -96,11 +129,11 @@ impl Config {
         if let Ok(b) = raw.parse::<bool>() {
             Ok(ConfigValue::Bool(b))
 
-        } else if key == "ignored_players" || key == "rows" {
+        } else if key == "ignored_players" || key == "rows" || key == "art_providers" {
             let vec = raw.split(',').map(|s| s.trim().to_string()).collect();
             Ok(ConfigValue::Vec(vec))
 
-        } else if key == "update_interval" {
+        } else if key == "update_interval" || key == "cache_ttl" {
             let duration = Duration::from_millis(raw.parse::<u64>()?);
             Ok(ConfigValue::Duration(duration))
             
@@ -158,57 +191,81 @@ impl TryFrom<ConfigValue> for Duration {
     }
 }
 
-#[derive(Debug, Default)]
-struct CoverArt {
-    cache: RwLock<HashMap<String, String>>,
+// marker so a failed fetch is cached too, and not retried until the TTL elapses
+#[derive(Debug, Clone, Copy, PartialEq, Eq, thiserror::Error)]
+#[error("cache miss")]
+struct CacheMiss;
+
+type CacheEntry<V> = (Instant, Result<V, CacheMiss>);
+
+#[derive(Debug)]
+struct AsyncCache<K, V> {
+    entries: RwLock<HashMap<K, CacheEntry<V>>>,
+    ttl: Duration,
 }
 
-impl CoverArt {
-    pub fn cache(&self, release: String, artist: String, url: String) {
-        {
-            let mut cache = self.cache.write().unwrap();
-            cache.insert(format!("{release}_{artist}"), url);
+impl<K, V> AsyncCache<K, V>
+where
+    K: std::hash::Hash + Eq,
+    V: Clone,
+{
+    fn new(ttl: Duration) -> Self {
+        AsyncCache {
+            entries: RwLock::new(HashMap::new()),
+            ttl,
         }
     }
 
-    pub fn has(&self, key: &str) -> bool {
+    async fn get<F>(&self, key: K, fetch: F) -> Result<V, CacheMiss>
+    where
+        F: std::future::Future<Output = Result<V, CacheMiss>>,
+    {
         {
-            let cache = self.cache.read().unwrap();
-            if let Some(_) = cache.get(key) {
-                return true;
-            } else {
-                return false;
+            let entries = self.entries.read().unwrap();
+            if let Some((stored_at, value)) = entries.get(&key) {
+                if stored_at.elapsed() <= self.ttl {
+                    return value.clone();
+                }
             }
         }
-    }
 
-    pub fn get(&self, key: String) -> String {
+        let value = fetch.await;
+
         {
-            let cache = self.cache.read().unwrap();
-            
-            if let Some(v) = cache.get(&key) {
-                return v.to_string();
-            } else {
-                return String::new();
-            }
+            let mut entries = self.entries.write().unwrap();
+            entries.insert(key, (Instant::now(), value.clone()));
         }
+
+        value
     }
 }
 
-static COVER_ART_CACHE: Lazy<Mutex<CoverArt>> = Lazy::new(|| {
-    Mutex::new(CoverArt {
-        cache: RwLock::new(HashMap::new()),
-    })
+fn cache_ttl() -> Duration {
+    read_config().get("cache_ttl").unwrap_or(Duration::from_secs(3600))
+}
+
+static COVER_ART_CACHE: Lazy<AsyncCache<String, String>> = Lazy::new(|| {
+    AsyncCache::new(cache_ttl())
 });
 
-async fn get_cover_art(current: Current) -> Result<String, Box<dyn Error>> {
-    let cover_art = COVER_ART_CACHE.lock().unwrap();
-    let key = format!("{}_{}", current.release, current.artist);
+// tried in the order given by the art_providers config key
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ArtProvider {
+    Spotify,
+    MusicBrainz,
+}
 
-    if cover_art.has(&key) {
-        return Ok(cover_art.get(key));
+impl ArtProvider {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "spotify" => Some(ArtProvider::Spotify),
+            "musicbrainz" => Some(ArtProvider::MusicBrainz),
+            _ => None,
+        }
     }
+}
 
+async fn get_musicbrainz_art(current: &Current) -> Result<String, CacheMiss> {
     let query = ReleaseSearchQuery::query_builder()
         .release(&escape(&current.release))
         .and()
@@ -217,27 +274,67 @@ async fn get_cover_art(current: Current) -> Result<String, Box<dyn Error>> {
 
     let results = Release::search(query)
         .execute()
-        .await?;
+        .await
+        .map_err(|_| CacheMiss)?;
 
-    if let Some(release) = results.entities.first() {
-        let mbid = &release.id;
+    let release = results.entities.first().ok_or(CacheMiss)?;
+    let mbid = &release.id;
 
-        if mbid == "1735e086-462e-42c3-b615-eebbd5e9f352" { // Nagios check release. This is what gets returned for "", "".
-            return Err("could not find cover art".into());
-        }
+    if mbid == "1735e086-462e-42c3-b615-eebbd5e9f352" { // Nagios check release. This is what gets returned for "", "".
+        return Err(CacheMiss);
+    }
+
+    Ok(format!("https://coverartarchive.org/release/{mbid}/front"))
+}
 
-        let url = format!("https://coverartarchive.org/release/{mbid}/front");
-        cover_art.cache(current.release, current.artist, url.clone());
-        
-        drop(cover_art);
+async fn get_spotify_art(current: &Current) -> Result<String, CacheMiss> {
+    let token: String = read_config().get("spotify_token").map_err(|_| CacheMiss)?;
+
+    let response = reqwest::Client::new()
+        .get("https://api.spotify.com/v1/search")
+        .bearer_auth(token)
+        .query(&[
+            ("q", format!("album:{} artist:{}", current.release, current.artist)),
+            ("type", "album".to_string()),
+            ("limit", "1".to_string()),
+        ])
+        .send()
+        .await
+        .map_err(|_| CacheMiss)?;
+
+    let body: serde_json::Value = response.json().await.map_err(|_| CacheMiss)?;
+
+    body["albums"]["items"][0]["images"][0]["url"]
+        .as_str()
+        .map(|s| s.to_string())
+        .ok_or(CacheMiss)
+}
 
-        return Ok(url);
-    } else {
-        return Err(format!("could not find release {}", current.release).into());
-    }
+async fn resolve_art(current: &Current) -> Result<String, CacheMiss> {
+    let key = format!("{}_{}", current.release, current.artist);
+
+    COVER_ART_CACHE.get(key, async {
+        let providers: Vec<String> = read_config()
+            .get("art_providers")
+            .unwrap_or_else(|_| vec!["musicbrainz".to_string()]);
+
+        for name in providers {
+            let result = match ArtProvider::parse(&name) {
+                Some(ArtProvider::Spotify) => get_spotify_art(current).await,
+                Some(ArtProvider::MusicBrainz) => get_musicbrainz_art(current).await,
+                None => continue,
+            };
+
+            if let Ok(url) = result {
+                return Ok(url);
+            }
+        }
+
+        Err(CacheMiss)
+    }).await
 }
 
-#[derive(Debug, PartialEq, Eq, Clone)]
+#[derive(Debug, PartialEq, Eq, Clone, serde::Serialize)]
 struct ActivityInfo {
     details: Arc<str>,  // 1st row
     state: Arc<str>,    // 2nd row
@@ -278,11 +375,25 @@ fn value_to_string(val: &MetadataValue) -> String {
     }
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize)]
+enum PresenceState {
+    Playing,
+    Paused,
+    Stopped,
+    #[default]
+    Idle,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
 struct Current {
     release: String,
     artist: String,
     url: String,
+    player: String,
+    status: PresenceState,
+    // (start, end) anchors for Discord's progress bar; None if no position reported
+    #[serde(skip)]
+    timestamps: Option<(SystemTime, SystemTime)>,
     activity: ActivityInfo,
 }
 
@@ -301,7 +412,10 @@ impl Current {
             release: String::new(),
             artist: String::new(),
             url: String::new(),
-            activity: activity.unwrap_or_else(ActivityInfo::default),
+            player: String::new(),
+            status: PresenceState::default(),
+            timestamps: None,
+            activity: activity.unwrap_or_default(),
         }
     }
 }
@@ -316,101 +430,266 @@ static CURRENT: Lazy<Mutex<Current>> = Lazy::new(|| {
     Mutex::new(Current::default())
 });
 
+#[derive(Debug, serde::Serialize)]
+struct StatusRecord {
+    #[serde(rename = "type")]
+    kind: PresenceState,
+    details: Arc<str>,
+    state: Arc<str>,
+    subtitle: Arc<str>,
+    image: Arc<str>,
+    player: String,
+}
+
+fn emit_status(current: &Current) {
+    let json_output: bool = read_config().get("json_output").unwrap_or(false);
+
+    if !json_output {
+        return;
+    }
+
+    let record = StatusRecord {
+        kind: current.status,
+        details: current.activity.details.clone(),
+        state: current.activity.state.clone(),
+        subtitle: current.activity.subtitle.clone(),
+        image: current.activity.image.clone(),
+        player: current.player.clone(),
+    };
+
+    if let Ok(line) = serde_json::to_string(&record) {
+        println!("{line}");
+    }
+}
+
 fn set_current(new: Current) {
-    let mut current = CURRENT.lock().unwrap();
-    *current = new;
+    {
+        let mut current = CURRENT.lock().unwrap();
+        *current = new.clone();
+    }
+    emit_status(&new);
 }
 
 fn reset_current() {
-    let mut current = CURRENT.lock().unwrap();
-    *current = Current::default();
+    let idle = Current::default();
+
+    {
+        let mut current = CURRENT.lock().unwrap();
+        *current = idle.clone();
+    }
+    emit_status(&idle);
 }
 
 static FILTER: Lazy<Regex> = Lazy::new(|| {
     Regex::new("^.*?\\{([^}]+)\\}.*?$").unwrap()
 });
 
-async fn process_metadata() -> Result<Current, AppError> {
-    let config = CONFIG.lock().unwrap();
-    let ignored_players: Vec<String> = config.get("ignored_players")?;
+const DISCORD_FIELD_LIMIT: usize = 128;
 
-    let show_paused: bool = config.get("show_paused")?;
-    let show_stopped: bool = config.get("show_stopped")?;
+// per-row marquee scroll offset (details/state/subtitle)
+static SCROLL_OFFSETS: Lazy<Mutex<[usize; 3]>> = Lazy::new(|| Mutex::new([0; 3]));
 
-    let mut players = PlayerFinder::new()?
-        .find_all()?;
+fn reset_scroll_offsets() {
+    let mut offsets = SCROLL_OFFSETS.lock().unwrap();
+    *offsets = [0; 3];
+}
 
-    players.retain(|p| !ignored_players.contains(&p.identity().to_string()));
+fn truncate_row(row: &str, limit: usize) -> String {
+    let graphemes: Vec<&str> = row.graphemes(true).collect();
 
-    if players.len() == 0 {
-        return Err(AppError::NoActivePlayers);
+    if graphemes.len() <= limit {
+        return row.to_string();
     }
 
-    let player = &players[0]; // just get the first one, since with .find_active(), players can't be ignored
+    let mut truncated: String = graphemes[..limit.saturating_sub(1)].concat();
+    truncated.push('…');
+    truncated
+}
 
-    let playback_status = player.get_playback_status()?;
-    
-    let mut player_name = player.identity().to_string().to_lowercase();
+fn scroll_row(row: &str, limit: usize, offset: &mut usize) -> String {
+    let graphemes: Vec<&str> = row.graphemes(true).collect();
 
-    if show_stopped && playback_status == PlaybackStatus::Stopped {
-        return Ok(Current::new(Some(ActivityInfo {
-            details: "Stopped playback".into(),
-            state: "".into(),
-            subtitle: "".into(),
-            image: player_name.into(),
-        })));
+    if graphemes.len() <= limit {
+        return row.to_string();
     }
 
-    if (playback_status == PlaybackStatus::Paused && !show_paused) || playback_status == PlaybackStatus::Stopped {
-        return Err(AppError::NoSongPlaying);
-    }
-    
-    let metadata = player.get_metadata().expect("could not get metadata");
+    let padded: Vec<&str> = row.graphemes(true).chain("   ".graphemes(true)).collect();
+    let len = padded.len();
+
+    *offset %= len;
+
+    let window: String = (0..limit)
+        .map(|i| padded[(*offset + i) % len])
+        .collect();
+
+    *offset += 1;
+
+    window
+}
+
+// recomputes the progress bar anchors on a track change or seek; otherwise keeps
+// `prev` so the bar doesn't jitter. Frozen while paused since position stops moving.
+fn resync_timestamps(
+    prev: Option<(SystemTime, SystemTime)>,
+    position: Option<Duration>,
+    length: Option<Duration>,
+    track_changed: bool,
+    is_paused: bool,
+    update_interval: Duration,
+) -> Option<(SystemTime, SystemTime)> {
+    let (position, length) = match (position, length) {
+        (Some(position), Some(length)) => (position, length),
+        _ => return None, // no position reported (live streams, paused-with-show_paused, ...)
+    };
 
-    let current = CURRENT.lock().unwrap();
+    if is_paused && prev.is_some() && !track_changed {
+        return prev;
+    }
 
-    let mut new = Current {
-        release: metadata.album_name().unwrap().to_string(),
-        artist: metadata.album_artists().unwrap().join(", "),
-        url: current.url.clone(),
-        activity: ActivityInfo::default(),
+    let seeked = match prev {
+        Some((start, _)) if !track_changed => match SystemTime::now().duration_since(start) {
+            Ok(expected) => position.abs_diff(expected) > update_interval,
+            Err(_) => true,
+        },
+        _ => true,
     };
 
-    if new.release == current.release && !current.activity.is_empty() {
-        return Ok(current.clone());
+    if !seeked {
+        return prev;
     }
 
-    drop(current);
+    let start = SystemTime::now() - position;
+    Some((start, start + length))
+}
+
+fn to_millis(time: SystemTime) -> i64 {
+    time.duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+async fn process_metadata() -> Result<Current, AppError> {
+    let (mut new, player_name, mut ret, fetch_cover_art) = {
+        let config = CONFIG.lock().unwrap();
+        let ignored_players: Vec<String> = config.get("ignored_players")?;
+
+        let show_paused: bool = config.get("show_paused")?;
+        let show_stopped: bool = config.get("show_stopped")?;
+        let scroll_rows: bool = config.get("scroll_rows").unwrap_or(false);
 
-    let rows: Vec<String> = config.get("rows")?;
+        let mut players = PlayerFinder::new()?
+            .find_all()?;
 
-    let mut ret = Vec::with_capacity(4);
+        players.retain(|p| !ignored_players.contains(&p.identity().to_string()));
+
+        if players.is_empty() {
+            return Err(AppError::NoActivePlayers);
+        }
+
+        let player = &players[0]; // just get the first one, since with .find_active(), players can't be ignored
+
+        let playback_status = player.get_playback_status()?;
     
-    for raw_row in rows.into_iter().take(3) {
-        let field = FILTER.replace_all(&raw_row, "$1").to_string();
-        let key = format!("xesam:{field}");
+        let mut player_name = player.identity().to_string().to_lowercase();
+
+        if show_stopped && playback_status == PlaybackStatus::Stopped {
+            return Ok(Current {
+                player: player_name.clone(),
+                status: PresenceState::Stopped,
+                ..Current::new(Some(ActivityInfo {
+                    details: "Stopped playback".into(),
+                    state: "".into(),
+                    subtitle: "".into(),
+                    image: player_name.into(),
+                }))
+            });
+        }
 
-        if let Some(val) = metadata.get(&key) {
-            ret.push(raw_row.replace(&format!("{{{field}}}"), &value_to_string(val)));
-        } else {
-            return Err(AppError::FieldNotFound(field));
+        if (playback_status == PlaybackStatus::Paused && !show_paused) || playback_status == PlaybackStatus::Stopped {
+            return Err(AppError::NoSongPlaying);
+        }
+    
+        let metadata = player.get_metadata()?;
+        let position = player.get_position().ok();
+        let length = metadata.length();
+
+        let current = CURRENT.lock().unwrap();
+
+        let mut new = Current {
+            release: metadata.album_name().ok_or_else(|| AppError::FieldNotFound("album".to_string()))?.to_string(),
+            artist: metadata.album_artists().ok_or_else(|| AppError::FieldNotFound("artist".to_string()))?.join(", "),
+            url: current.url.clone(),
+            player: player_name.clone(),
+            status: if playback_status == PlaybackStatus::Paused {
+                PresenceState::Paused
+            } else {
+                PresenceState::Playing
+            },
+            timestamps: current.timestamps,
+            activity: ActivityInfo::default(),
+        };
+
+        let track_changed = new.release != current.release;
+
+        let update_interval: Duration = config.get("update_interval")?;
+        let is_paused = playback_status == PlaybackStatus::Paused;
+        new.timestamps = resync_timestamps(new.timestamps, position, length, track_changed, is_paused, update_interval);
+
+        if !track_changed && !current.activity.is_empty() && !scroll_rows {
+            let mut cached = current.clone();
+            cached.player = new.player;
+            cached.status = new.status;
+            cached.timestamps = new.timestamps;
+            return Ok(cached);
         }
-    }
 
-    while ret.len() < 3 {
-        ret.push(String::new());
-    }
+        drop(current);
 
-    if playback_status == PlaybackStatus::Paused && show_paused {
-        player_name.push_str("_paused");
-    }
+        if track_changed {
+            reset_scroll_offsets();
+        }
 
-    let fetch_cover_art: bool = config.get("fetch_cover_art")?;
+        let rows: Vec<String> = config.get("rows")?;
 
-    drop(config);
+        let mut ret = Vec::with_capacity(4);
+    
+        for raw_row in rows.into_iter().take(3) {
+            let field = FILTER.replace_all(&raw_row, "$1").to_string();
+            let key = format!("xesam:{field}");
+
+            if let Some(val) = metadata.get(&key) {
+                ret.push(raw_row.replace(&format!("{{{field}}}"), &value_to_string(val)));
+            } else {
+                return Err(AppError::FieldNotFound(field));
+            }
+        }
+
+        while ret.len() < 3 {
+            ret.push(String::new());
+        }
+
+        if scroll_rows {
+            let mut offsets = SCROLL_OFFSETS.lock().unwrap();
+            for (row, offset) in ret.iter_mut().zip(offsets.iter_mut()) {
+                *row = scroll_row(row, DISCORD_FIELD_LIMIT, offset);
+            }
+        } else {
+            for row in ret.iter_mut() {
+                *row = truncate_row(row, DISCORD_FIELD_LIMIT);
+            }
+        }
+
+        if playback_status == PlaybackStatus::Paused && show_paused {
+            player_name.push_str("_paused");
+        }
+
+        let fetch_cover_art: bool = config.get("fetch_cover_art")?;
+
+        (new, player_name, ret, fetch_cover_art)
+    };
 
     if fetch_cover_art {
-        match get_cover_art(new.clone()).await {
+        match resolve_art(&new).await {
             Ok(url) => {
                 new.url = url;
             },
@@ -448,23 +727,40 @@ async fn main() -> Result<(), Box<dyn Error>> {
     client.connect_and_wait()?.filter()?;
     
     let mut listening = false;
+    let mut consecutive_transient: u32 = 0;
 
     loop {
         match process_metadata().await {
             Ok(new) => {
                 listening = true;
+                consecutive_transient = 0;
                 let rows = &new.activity;
 
-                if *CURRENT.lock().unwrap() != new {
+                let changed = {
+                    let prev = CURRENT.lock().unwrap();
+                    *prev != new
+                        || prev.activity != new.activity
+                        || prev.timestamps != new.timestamps
+                        || prev.status != new.status
+                        || prev.player != new.player
+                };
+
+                if changed {
                     set_current(new.clone());
 
-                    let activity = Activity::new()
+                    let mut activity = Activity::new()
                         .kind(ActivityType::Listening)
                         .details(&*rows.details)
                         .state(&*rows.state)
                         .assets(Assets::new()
                             .large_image(&*rows.image, Some(&*rows.subtitle)));
 
+                    if let Some((start, end)) = new.timestamps {
+                        activity = activity.timestamps(Timestamps::new()
+                            .start(to_millis(start))
+                            .end(to_millis(end)));
+                    }
+
                     let activity_packet = Packet::new_activity(Some(&activity), None);
 
                     if let Err(why) = client.send_and_wait(activity_packet)?.filter() {
@@ -472,11 +768,28 @@ async fn main() -> Result<(), Box<dyn Error>> {
                     }
                 }
             },
-            Err(_) => {
-                if listening != false {
-                    listening = false;
-                    reset_current();
-                    let _ = client.send_and_wait(Packet::new_activity(None, None))?.filter(); // send a blank packet to clear the rich presence
+            Err(err) => match err.classify() {
+                ErrorClass::Expected => {
+                    consecutive_transient = 0;
+
+                    if listening {
+                        listening = false;
+                        reset_current();
+                        let _ = client.send_and_wait(Packet::new_activity(None, None))?.filter(); // send a blank packet to clear the rich presence
+                    }
+                },
+                ErrorClass::Transient => {
+                    consecutive_transient += 1;
+                    eprintln!("transient error, keeping last presence: {err}");
+
+                    // exponential backoff so a flapping player bus doesn't spam Discord
+                    let backoff = interval * 2u32.pow(consecutive_transient.min(5));
+                    sleep(backoff).await;
+                    continue;
+                },
+                ErrorClass::Fatal => {
+                    eprintln!("fatal error: {err}");
+                    return Err(err.into());
                 }
             }
         }